@@ -1,10 +1,11 @@
 //! This module provides functionality for querying callable information about a token.
 
 use either::Either;
-use hir::{Semantics, Type};
+use hir::{HasAttrs, Semantics, Type};
+use stdx::format_to;
 use syntax::{
     ast::{self, HasArgList, HasName},
-    AstNode, SyntaxToken,
+    AstNode, SyntaxToken, TextRange, TextSize,
 };
 
 use crate::RootDatabase;
@@ -38,10 +39,101 @@ impl ActiveParameter {
     }
 }
 
+/// Contains information about a call site. Specifically the signature of the callable, the
+/// ranges of its parameters within that signature and which parameter, if any, is currently
+/// active.
+#[derive(Debug)]
+pub struct CallInfo {
+    pub doc: Option<hir::Documentation>,
+    pub signature: String,
+    pub active_parameter: Option<usize>,
+    parameters: Vec<TextRange>,
+}
+
+impl CallInfo {
+    pub fn parameter_labels(&self) -> impl Iterator<Item = &str> + '_ {
+        self.parameters.iter().map(move |&it| &self.signature[it])
+    }
+
+    pub fn parameter_ranges(&self) -> &[TextRange] {
+        &self.parameters
+    }
+
+    fn push_param(&mut self, param: &str) {
+        if !self.signature.ends_with('(') {
+            self.signature.push_str(", ");
+        }
+        let start = TextSize::of(&self.signature);
+        self.signature.push_str(param);
+        let end = TextSize::of(&self.signature);
+        self.parameters.push(TextRange::new(start, end));
+    }
+}
+
+/// Computes parameter information for the given token of a function or method call.
+pub fn call_info(sema: &Semantics<RootDatabase>, token: SyntaxToken) -> Option<CallInfo> {
+    let (callable, active_parameter) = callable_for_token(sema, token)?;
+
+    let doc = match callable.kind() {
+        hir::CallableKind::Function(it) => it.docs(sema.db),
+        hir::CallableKind::TupleStruct(it) => it.docs(sema.db),
+        hir::CallableKind::TupleEnumVariant(it) => it.docs(sema.db),
+        hir::CallableKind::Closure | hir::CallableKind::FnPtr | hir::CallableKind::FnImpl(_) => {
+            None
+        }
+    };
+
+    let mut res =
+        CallInfo { doc, signature: String::new(), active_parameter, parameters: Vec::new() };
+
+    res.signature.push('(');
+    for (pat, ty) in callable.params(sema.db) {
+        let mut param = String::new();
+        match pat {
+            Some(Either::Left(_)) => format_to!(param, "self"),
+            Some(Either::Right(pat)) => format_to!(param, "{}", pat),
+            None => {}
+        }
+        if !param.is_empty() {
+            param.push_str(": ");
+        }
+        format_to!(param, "{}", ty.display(sema.db));
+        res.push_param(&param);
+    }
+    res.signature.push(')');
+
+    Some(res)
+}
+
 /// Returns a [`hir::Callable`] this token is a part of and its argument index of said callable.
 pub fn callable_for_token(
     sema: &Semantics<RootDatabase>,
     token: SyntaxToken,
+) -> Option<(hir::Callable, Option<usize>)> {
+    // `token` may itself originate from a macro expansion, e.g. an argument passed through a
+    // user macro that ultimately expands to a call. Descend into any macro expansions the
+    // token is part of and retry on each expanded token, so the search below operates on
+    // whichever syntax tree actually contains the call. For a token that isn't inside a macro
+    // call this simply yields the token itself.
+    // Trivia inside a macro call's token tree has no counterpart in the expansion, so such a
+    // token is descended from the closest preceding non-trivia token instead.
+    let in_token_tree = token.kind().is_trivia()
+        && token
+            .parent()
+            .map_or(false, |it| it.ancestors().any(|it| ast::TokenTree::can_cast(it.kind())));
+    let token = if in_token_tree {
+        syntax::algo::skip_trivia_token(token, syntax::Direction::Prev)?
+    } else {
+        token
+    };
+    sema.descend_into_macros(token)
+        .into_iter()
+        .find_map(|token| callable_for_token_impl(sema, token))
+}
+
+fn callable_for_token_impl(
+    sema: &Semantics<RootDatabase>,
+    token: SyntaxToken,
 ) -> Option<(hir::Callable, Option<usize>)> {
     // Find the calling expression and it's NameRef
     let parent = token.parent()?;
@@ -58,6 +150,12 @@ pub fn callable_for_token(
         ast::CallableExpr::MethodCall(call) => sema.resolve_method_call_as_callable(call),
     }?;
     let active_param = if let Some(arg_list) = calling_node.arg_list() {
+        // A plain `Call` whose callee resolves to an associated function with a `self`
+        // parameter (UFCS, e.g. `Vec::push(&mut v, item)`) is *not* special-cased here:
+        // `as_callable` is not a bound method, so `self` stays in `Callable::params` as
+        // ordinary parameter 0, lining up with the receiver's syntactic argument slot 0
+        // without any adjustment. Only `resolve_method_call_as_callable` (dotted method
+        // calls, where the receiver isn't a syntactic argument at all) strips `self`.
         let param = arg_list
             .args()
             .take_while(|arg| arg.syntax().text_range().end() <= token.text_range().start())
@@ -118,3 +216,170 @@ pub fn generics_for_token(
         None
     }
 }
+
+/// The concrete generic parameter (lifetime, type or const) a turbofish argument position
+/// refers to, mirroring [`ActiveParameter`] for value arguments.
+#[derive(Debug)]
+pub struct ActiveGenericParameter {
+    pub param: hir::GenericParam,
+}
+
+impl ActiveGenericParameter {
+    /// Returns information about the generic argument this token is part of.
+    pub fn at_token(sema: &Semantics<RootDatabase>, token: SyntaxToken) -> Option<Self> {
+        let (generic_def, active_parameter) = generics_for_token(sema, token)?;
+
+        // `GenericDef::params` is already in canonical declaration order: lifetimes first,
+        // then types and consts, with `Self` as the implicit first entry of the type/const
+        // list for a trait. `Self` is never written out in a turbofish, so skip it — but only
+        // after the lifetime params, which come before it and are never touched.
+        let mut params = generic_def.params(sema.db);
+        if let hir::GenericDef::Trait(_) = generic_def {
+            let lifetime_count = params
+                .iter()
+                .take_while(|param| matches!(param, hir::GenericParam::LifetimeParam(_)))
+                .count();
+            if lifetime_count < params.len() {
+                params.remove(lifetime_count);
+            }
+        }
+
+        let param = params.into_iter().nth(active_parameter)?;
+        Some(ActiveGenericParameter { param })
+    }
+
+    pub fn name(&self, db: &RootDatabase) -> Option<hir::Name> {
+        match self.param {
+            hir::GenericParam::TypeParam(it) => it.name(db),
+            hir::GenericParam::LifetimeParam(it) => Some(it.name(db)),
+            hir::GenericParam::ConstParam(it) => it.name(db),
+        }
+    }
+
+    /// The default of a type generic parameter, if this one has one.
+    pub fn default(&self, db: &RootDatabase) -> Option<Type> {
+        match self.param {
+            hir::GenericParam::TypeParam(it) => it.default(db),
+            hir::GenericParam::ConstParam(_) | hir::GenericParam::LifetimeParam(_) => None,
+        }
+    }
+
+    /// The declared type of a `const` generic parameter, if this is one.
+    pub fn ty(&self, db: &RootDatabase) -> Option<Type> {
+        match self.param {
+            hir::GenericParam::ConstParam(it) => Some(it.ty(db)),
+            hir::GenericParam::TypeParam(_) | hir::GenericParam::LifetimeParam(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use base_db::fixture::WithFixture;
+    use hir::Semantics;
+    use syntax::AstNode;
+
+    use crate::RootDatabase;
+
+    use super::*;
+
+    fn check_call_info(ra_fixture: &str) -> CallInfo {
+        let (db, position) = RootDatabase::with_position(ra_fixture);
+        let sema = Semantics::new(&db);
+        let file = sema.parse(position.file_id);
+        let token = file
+            .syntax()
+            .token_at_offset(position.offset)
+            .left_biased()
+            .expect("no token at the marked position");
+        call_info(&sema, token).expect("expected call info at the marked position")
+    }
+
+    #[test]
+    fn call_info_renders_signature_and_parameter_ranges() {
+        let info = check_call_info(
+            r#"
+fn foo(a: u32, b: i32) {}
+fn main() { foo(1, $0); }
+"#,
+        );
+        assert_eq!(info.signature, "(a: u32, b: i32)");
+        assert_eq!(info.active_parameter, Some(1));
+        assert_eq!(info.parameter_labels().collect::<Vec<_>>(), vec!["a: u32", "b: i32"]);
+    }
+
+    #[test]
+    fn active_generic_parameter_accounts_for_lifetimes_before_self() {
+        let (db, position) = RootDatabase::with_position(
+            r#"
+trait Foo<'a, T> {
+    fn foo() {}
+}
+struct S;
+impl<'a> Foo<'a, u32> for S {}
+fn bar<'x>() {
+    <S as Foo<'x, $0u32>>::foo();
+}
+"#,
+        );
+        let sema = Semantics::new(&db);
+        let file = sema.parse(position.file_id);
+        let token = file
+            .syntax()
+            .token_at_offset(position.offset)
+            .left_biased()
+            .expect("no token at the marked position");
+
+        // The lifetime `'x` occupies index 0; the type argument after it must not be
+        // mistaken for the implicit `Self` that `GenericDef::params` prepends to the
+        // type/const portion of the list.
+        let active = ActiveGenericParameter::at_token(&sema, token)
+            .expect("expected an active generic parameter");
+        assert!(matches!(active.param, hir::GenericParam::TypeParam(_)));
+    }
+
+    #[test]
+    fn callable_for_token_descends_into_macro_expansion() {
+        let info = check_call_info(
+            r#"
+macro_rules! id {
+    ($e:expr) => { $e };
+}
+fn foo(a: u32, b: i32) {}
+fn main() { id!(foo(1, $0)); }
+"#,
+        );
+        assert_eq!(info.signature, "(a: u32, b: i32)");
+        assert_eq!(info.active_parameter, Some(1));
+    }
+
+    #[test]
+    fn ufcs_call_active_parameter_lines_up_with_self_at_index_zero() {
+        let (db, position) = RootDatabase::with_position(
+            r#"
+struct S;
+impl S {
+    fn set(&mut self, a: u32, b: u32) {}
+}
+fn main() {
+    let mut s = S;
+    S::set(&mut s, 1, $0);
+}
+"#,
+        );
+        let sema = Semantics::new(&db);
+        let file = sema.parse(position.file_id);
+        let token = file
+            .syntax()
+            .token_at_offset(position.offset)
+            .left_biased()
+            .expect("no token at the marked position");
+
+        // The receiver `&mut s` is syntactic argument 0 and lines up with `self` as
+        // parameter 0 of the resolved `Callable` (a plain `Call`, not a bound method call,
+        // keeps `self` in `params()`), so counting syntactic arguments directly still lands
+        // on `b` for the third argument slot.
+        let active = ActiveParameter::at_token(&sema, token).expect("expected an active parameter");
+        assert_eq!(active.ident().unwrap().to_string(), "b");
+    }
+}